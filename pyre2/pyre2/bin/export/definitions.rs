@@ -21,6 +21,7 @@ use ruff_python_ast::StmtExpr;
 use ruff_text_size::TextRange;
 use starlark_map::small_map::Entry;
 use starlark_map::small_map::SmallMap;
+use starlark_map::small_set::SmallSet;
 
 use crate::ast::Ast;
 use crate::config::Config;
@@ -57,6 +58,10 @@ pub struct Definitions {
     pub import_all: SmallMap<ModuleName, TextRange>,
     /// The `__all__` variable contents.
     pub dunder_all: Vec<DunderAllEntry>,
+    /// For a name bound by `from x import y [as z]`, the module `x` it came
+    /// from. Not populated for `Local`, `ImportModule`, or names introduced
+    /// by a `from x import *` (those are tracked in `import_all` instead).
+    pub import_sources: SmallMap<Name, ModuleName>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -124,6 +129,45 @@ impl Definitions {
         self.import_all.entry(ModuleName::builtins()).or_default();
     }
 
+    /// The names this module genuinely exposes to `from this_module import *`
+    /// (or to a consumer asking "what does this module export?"): entries of
+    /// an explicit `__all__` when present, otherwise whatever `ensure_dunder_all`
+    /// would have synthesized (non-underscore `Local`/`ImportAsEq` bindings).
+    ///
+    /// `__all__` entries that splice in another module's `__all__` (e.g.
+    /// `__all__ += foo.__all__`) are resolved via `flatten_dunder_all`, so
+    /// `modules` must contain the `Definitions` of every module this one's
+    /// `__all__` can reach, or those re-exported names are reported as
+    /// unresolved and dropped. Such names may not have a binding of their
+    /// own in `self.definitions` (they can arrive purely via `import *`), in
+    /// which case they're reported with `DefinitionStyle::Import` and the
+    /// location of the `__all__` entry that names them.
+    pub fn public_names(
+        &self,
+        this_module: ModuleName,
+        modules: &SmallMap<ModuleName, Definitions>,
+    ) -> Vec<(Name, TextRange, DefinitionStyle)> {
+        if self.definitions.contains_key(&dunder::ALL) || !self.dunder_all.is_empty() {
+            let (names, _unresolved) = self.flatten_dunder_all(this_module, modules);
+            names
+                .into_iter()
+                .map(|(name, range)| match self.definitions.get(&name) {
+                    Some((def_range, style, _)) => (name, *def_range, *style),
+                    None => (name, range, DefinitionStyle::Import),
+                })
+                .collect()
+        } else {
+            self.definitions
+                .iter()
+                .filter(|(name, (_, style, _))| {
+                    !name.starts_with('_')
+                        && matches!(style, DefinitionStyle::Local | DefinitionStyle::ImportAsEq)
+                })
+                .map(|(name, (range, style, _))| (name.clone(), *range, *style))
+                .collect()
+        }
+    }
+
     pub fn ensure_dunder_all(&mut self, style: ModuleStyle) {
         if self.definitions.contains_key(&dunder::ALL) {
             // Explicitly defined, so don't redefine it
@@ -144,6 +188,73 @@ impl Definitions {
             }
         }
     }
+
+    /// Flatten `dunder_all` into a final, de-duplicated, order-preserving
+    /// list of names (each paired with the location of the `__all__` entry
+    /// that names it): a `Name` entry appends the name, a `Module` entry
+    /// splices in the full flattened `__all__` of that module (recursively,
+    /// via `modules`), and a `Remove` entry deletes the most recent matching
+    /// name. Returns the flattened names plus any `Module` references that
+    /// couldn't be found in `modules`, so missing re-export targets can be
+    /// reported.
+    ///
+    /// Module `__all__`s can reference each other (package `a`'s `__all__`
+    /// pulls in `b`'s, and vice versa, as happens with `_collections_abc`),
+    /// so a visited set is threaded through the recursion: re-entering a
+    /// module contributes the empty list instead of looping forever.
+    pub fn flatten_dunder_all(
+        &self,
+        this_module: ModuleName,
+        modules: &SmallMap<ModuleName, Definitions>,
+    ) -> (Vec<(Name, TextRange)>, Vec<ModuleName>) {
+        let mut visited = SmallSet::new();
+        visited.insert(this_module);
+        let mut unresolved = Vec::new();
+        let names = Self::flatten_entries(&self.dunder_all, modules, &mut visited, &mut unresolved);
+        (names, unresolved)
+    }
+
+    fn flatten_entries(
+        entries: &[DunderAllEntry],
+        modules: &SmallMap<ModuleName, Definitions>,
+        visited: &mut SmallSet<ModuleName>,
+        unresolved: &mut Vec<ModuleName>,
+    ) -> Vec<(Name, TextRange)> {
+        let mut result: Vec<(Name, TextRange)> = Vec::new();
+        for entry in entries {
+            match entry {
+                DunderAllEntry::Name(range, name) => {
+                    if !result.iter().any(|(n, _)| n == name) {
+                        result.push((name.clone(), *range));
+                    }
+                }
+                DunderAllEntry::Module(_, module) => {
+                    if !visited.insert(*module) {
+                        // Cycle: the re-entered module contributes nothing.
+                        continue;
+                    }
+                    match modules.get(module) {
+                        Some(defs) => {
+                            for (name, range) in
+                                Self::flatten_entries(&defs.dunder_all, modules, visited, unresolved)
+                            {
+                                if !result.iter().any(|(n, _)| *n == name) {
+                                    result.push((name, range));
+                                }
+                            }
+                        }
+                        None => unresolved.push(*module),
+                    }
+                }
+                DunderAllEntry::Remove(_, name) => {
+                    if let Some(pos) = result.iter().rposition(|(n, _)| n == name) {
+                        result.remove(pos);
+                    }
+                }
+            }
+        }
+        result
+    }
 }
 
 impl<'a> DefinitionsBuilder<'a> {
@@ -201,13 +312,14 @@ impl<'a> DefinitionsBuilder<'a> {
                 }
             }
             Stmt::ImportFrom(x) => {
+                let module = self.module_name.new_maybe_relative(
+                    self.is_init,
+                    x.level,
+                    x.module.as_ref().map(|x| &x.id),
+                );
                 for a in &x.names {
                     if &a.name == "*" {
-                        if let Some(module) = self.module_name.new_maybe_relative(
-                            self.is_init,
-                            x.level,
-                            x.module.as_ref().map(|x| &x.id),
-                        ) {
+                        if let Some(module) = module {
                             self.inner.import_all.insert(module, a.name.range);
                         }
                     } else {
@@ -218,14 +330,14 @@ impl<'a> DefinitionsBuilder<'a> {
                         } else {
                             DefinitionStyle::Import
                         };
-                        self.add_identifier(a.asname.as_ref().unwrap_or(&a.name), style);
+                        let bound = a.asname.as_ref().unwrap_or(&a.name);
+                        self.add_identifier(bound, style);
+                        if let Some(module) = module {
+                            self.inner.import_sources.insert(bound.id.clone(), module);
+                        }
                         if style == DefinitionStyle::ImportAsEq
                             && a.name.id == dunder::ALL
-                            && let Some(module) = self.module_name.new_maybe_relative(
-                                self.is_init,
-                                x.level,
-                                x.module.as_ref().map(|x| &x.id),
-                            )
+                            && let Some(module) = module
                         {
                             self.inner.dunder_all = vec![DunderAllEntry::Module(x.range, module)]
                         }
@@ -436,6 +548,132 @@ __all__.remove('r')
         );
     }
 
+    #[test]
+    fn test_public_names() {
+        let defs = check(
+            r#"
+def _hidden(): ...
+def shown(): ...
+import mod as mod
+"#,
+            &[],
+            &["_hidden", "shown", "mod"],
+        );
+        assert_eq!(
+            defs.public_names(ModuleName::from_str("main"), &SmallMap::new())
+                .into_iter()
+                .map(|(name, _, style)| (name, style))
+                .collect::<Vec<_>>(),
+            vec![
+                (Name::new("shown"), DefinitionStyle::Local),
+                (Name::new("mod"), DefinitionStyle::ImportAsEq),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_public_names_reexported_via_dunder_all_splice() {
+        // The `_collections_abc` idiom: `main` doesn't bind the names
+        // individually (they only arrive via `import *`), it just splices in
+        // `impl`'s `__all__`. `public_names` must still surface them.
+        let mut imp = check("def real_thing(): ...", &[], &["real_thing"]);
+        imp.ensure_dunder_all(ModuleStyle::Library);
+        let main = check(
+            r#"
+from impl import *
+from impl import __all__ as __all__
+"#,
+            &["impl"],
+            &["__all__"],
+        );
+        let mut modules = SmallMap::new();
+        modules.insert(ModuleName::from_str("impl"), imp);
+
+        let names = main
+            .public_names(ModuleName::from_str("main"), &modules)
+            .into_iter()
+            .map(|(name, _, style)| (name, style))
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec![(Name::new("real_thing"), DefinitionStyle::Import)]);
+    }
+
+    #[test]
+    fn test_import_sources() {
+        let defs = check(
+            r#"
+from bar import baz as qux
+from bar import moo
+import mod.ule
+"#,
+            &[],
+            &["qux", "moo", "mod"],
+        );
+        assert_eq!(
+            defs.import_sources
+                .iter()
+                .map(|(name, module)| (name.as_str(), module.as_str()))
+                .collect::<Vec<_>>(),
+            vec![("qux", "bar"), ("moo", "bar")],
+        );
+    }
+
+    #[test]
+    fn test_flatten_dunder_all() {
+        let defs = check(
+            r#"
+a = 1
+b = 1
+__all__ = ("a", "b")
+__all__.remove('a')
+"#,
+            &[],
+            &["a", "b", "__all__"],
+        );
+        let (names, unresolved) =
+            defs.flatten_dunder_all(ModuleName::from_str("main"), &SmallMap::new());
+        assert_eq!(names, vec![(Name::new("b"), TextRange::default())]);
+        assert!(unresolved.is_empty());
+    }
+
+    #[test]
+    fn test_flatten_dunder_all_cycle() {
+        // `a` and `b` each pull in the other's `__all__`, as happens with
+        // `_collections_abc`. Recursion must terminate instead of looping.
+        let a = check(
+            r#"
+from b import *
+x = 1
+__all__ = ["x"]
+__all__ += b.__all__
+"#,
+            &["b"],
+            &["x", "__all__"],
+        );
+        let b = check(
+            r#"
+from a import *
+y = 1
+__all__ = ["y"]
+__all__ += a.__all__
+"#,
+            &["a"],
+            &["y", "__all__"],
+        );
+        let mut modules = SmallMap::new();
+        modules.insert(ModuleName::from_str("a"), a.clone());
+        modules.insert(ModuleName::from_str("b"), b);
+
+        let (names, unresolved) = a.flatten_dunder_all(ModuleName::from_str("a"), &modules);
+        assert_eq!(
+            names,
+            vec![
+                (Name::new("x"), TextRange::default()),
+                (Name::new("y"), TextRange::default()),
+            ],
+        );
+        assert!(unresolved.is_empty());
+    }
+
     #[test]
     fn test_all_reexport() {
         // Not in the spec, but see collections.abc which does this.
@@ -0,0 +1,137 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! A reverse index from exported `Name` to the modules that export it,
+//! so the checker can offer "did you mean `from x import y`?" for an
+//! unresolved name. Analogous to rust-analyzer's `import_map`.
+
+use ruff_python_ast::name::Name;
+use ruff_text_size::TextRange;
+use starlark_map::small_map::SmallMap;
+
+use crate::export::definitions::DefinitionStyle;
+use crate::export::definitions::Definitions;
+use crate::module::module_name::ModuleName;
+
+/// Reverse index of `Name -> modules that export it`, built by aggregating
+/// the `Definitions` of every analyzed module. Rebuilding is per-module
+/// (`insert_module`/`remove_module`), so an incremental re-index touches
+/// only the modules that actually changed.
+#[derive(Debug, Clone, Default)]
+pub struct ImportIndex {
+    exports: SmallMap<Name, Vec<(ModuleName, DefinitionStyle, TextRange)>>,
+    /// The names each module last contributed, so `remove_module` only has
+    /// to touch that module's own entries instead of scanning the index.
+    contributed: SmallMap<ModuleName, Vec<Name>>,
+}
+
+impl ImportIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Merge in the public exports of `module`, replacing whatever it
+    /// contributed last time. Safe to call repeatedly as `module` is
+    /// re-analyzed: re-indexing it never leaves stale entries behind, and
+    /// touches only `module`'s own names. `modules` should contain the
+    /// `Definitions` of every analyzed module, so that `definitions`' own
+    /// `__all__` can be resolved across re-exports (see `public_names`).
+    pub fn insert_module(
+        &mut self,
+        module: ModuleName,
+        definitions: &Definitions,
+        modules: &SmallMap<ModuleName, Definitions>,
+    ) {
+        self.remove_module(module);
+        let mut names = Vec::new();
+        for (name, range, style) in definitions.public_names(module, modules) {
+            self.exports
+                .entry(name.clone())
+                .or_default()
+                .push((module, style, range));
+            names.push(name);
+        }
+        self.contributed.insert(module, names);
+    }
+
+    /// Drop everything `module` previously contributed. Only touches the
+    /// names `module` itself last exported, not the whole index.
+    pub fn remove_module(&mut self, module: ModuleName) {
+        let Some(names) = self.contributed.remove(&module) else {
+            return;
+        };
+        for name in names {
+            if let Some(entries) = self.exports.get_mut(&name) {
+                entries.retain(|(m, _, _)| *m != module);
+                if entries.is_empty() {
+                    self.exports.remove(&name);
+                }
+            }
+        }
+    }
+
+    /// The modules exporting `name`, and the location of each export.
+    pub fn lookup(&self, name: &Name) -> impl Iterator<Item = (ModuleName, TextRange)> + '_ {
+        self.exports
+            .get(name)
+            .into_iter()
+            .flatten()
+            .map(|(module, _, range)| (*module, *range))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::Ast;
+    use crate::config::Config;
+
+    fn definitions(contents: &str, module: ModuleName) -> Definitions {
+        let ast = Ast::parse(contents).0;
+        Definitions::new(&ast.body, module, false, &Config::default())
+    }
+
+    #[test]
+    fn test_lookup_across_modules() {
+        let mut index = ImportIndex::new();
+        let foo = ModuleName::from_str("foo");
+        let bar = ModuleName::from_str("bar");
+        index.insert_module(foo, &definitions("def thing(): ...", foo), &SmallMap::new());
+        index.insert_module(bar, &definitions("def thing(): ...", bar), &SmallMap::new());
+
+        let mut found = index
+            .lookup(&Name::new("thing"))
+            .map(|(m, _)| m.as_str().to_owned())
+            .collect::<Vec<_>>();
+        found.sort();
+        assert_eq!(found, vec!["bar".to_owned(), "foo".to_owned()]);
+    }
+
+    #[test]
+    fn test_reinsert_module_drops_stale_entries() {
+        // Re-indexing a module (e.g. after it's edited) must not leave
+        // entries for names it no longer defines.
+        let mut index = ImportIndex::new();
+        let bar = ModuleName::from_str("bar");
+        index.insert_module(bar, &definitions("def thing(): ...", bar), &SmallMap::new());
+        assert_eq!(index.lookup(&Name::new("thing")).count(), 1);
+
+        index.insert_module(bar, &definitions("def other(): ...", bar), &SmallMap::new());
+        assert_eq!(index.lookup(&Name::new("thing")).count(), 0);
+        assert_eq!(index.lookup(&Name::new("other")).count(), 1);
+    }
+
+    #[test]
+    fn test_remove_module() {
+        let mut index = ImportIndex::new();
+        let foo = ModuleName::from_str("foo");
+        index.insert_module(foo, &definitions("def thing(): ...", foo), &SmallMap::new());
+        assert_eq!(index.lookup(&Name::new("thing")).count(), 1);
+        index.remove_module(foo);
+        assert_eq!(index.lookup(&Name::new("thing")).count(), 0);
+    }
+}
@@ -0,0 +1,176 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Find the shortest, most public way to import a symbol that may be
+//! re-exported away from where it is defined (e.g. defined in `pkg._impl`
+//! but blessed at `pkg` via `from ._impl import Thing as Thing`).
+
+use ruff_python_ast::name::Name;
+use ruff_text_size::TextRange;
+use starlark_map::small_map::SmallMap;
+use starlark_map::small_set::SmallSet;
+
+use crate::export::definitions::DefinitionStyle;
+use crate::export::definitions::Definitions;
+use crate::module::module_name::ModuleName;
+use crate::types::qname::QName;
+
+/// Search across `modules` (every analyzed module's `Definitions`) for the
+/// shortest, most public path to import `defining` from `importing`.
+///
+/// This is a BFS over modules: the frontier starts at the module that
+/// directly defines the symbol, then expands to any not-yet-visited module
+/// that re-exports it -- either via an `Import`/`ImportAsEq` binding pointing
+/// back to a module already in the frontier (tracked in `import_sources`),
+/// via a `from x import *` of such a module, or via a `dunder_all` `Module`
+/// entry that pulls it in. The defining module
+/// itself is never returned just because it's where the search started --
+/// each expanded level is checked for a public definition before the next
+/// expansion, so a shallower re-export always wins the tie-break. Only once
+/// the graph is exhausted with no re-export found do we fall back to the
+/// defining module's own (possibly non-public) binding. A visited set guards
+/// cycles among `__init__` re-exports.
+pub fn find_path(
+    modules: &SmallMap<ModuleName, Definitions>,
+    defining: &QName,
+    importing: ModuleName,
+) -> Option<(ModuleName, TextRange)> {
+    let name = defining.id();
+    let origin = defining.module.name();
+    if origin == importing {
+        // Already visible in the importing module itself; no import needed.
+        return modules
+            .get(&origin)
+            .and_then(|defs| defs.definitions.get(name))
+            .map(|(range, _, _)| (origin, *range));
+    }
+
+    let fallback = modules
+        .get(&origin)
+        .and_then(|defs| defs.definitions.get(name))
+        .map(|(range, _, _)| (origin, *range));
+
+    let mut visited = SmallSet::new();
+    visited.insert(origin);
+    let mut frontier = vec![origin];
+
+    loop {
+        let mut next = Vec::new();
+        for (candidate, defs) in modules.iter() {
+            if visited.contains(candidate) {
+                continue;
+            }
+            if frontier
+                .iter()
+                .any(|module| reexports(defs, *module, name))
+            {
+                visited.insert(*candidate);
+                next.push(*candidate);
+            }
+        }
+        if next.is_empty() {
+            // Nothing (else) re-exports it; fall back to the defining module.
+            return fallback;
+        }
+
+        let candidates: Vec<(ModuleName, TextRange)> = next
+            .iter()
+            .filter_map(|module| {
+                let defs = modules.get(module)?;
+                let (range, style, _) = defs.definitions.get(name)?;
+                if is_public(defs, name, *style) {
+                    Some((*module, *range))
+                } else {
+                    None
+                }
+            })
+            .collect();
+        if let Some(best) = candidates
+            .into_iter()
+            .min_by_key(|(module, _)| dotted_components(*module))
+        {
+            return Some(best);
+        }
+
+        frontier = next;
+    }
+}
+
+fn is_public(defs: &Definitions, name: &Name, style: DefinitionStyle) -> bool {
+    style == DefinitionStyle::ImportAsEq
+        || defs
+            .dunder_all
+            .iter()
+            .any(|entry| matches!(entry, crate::export::definitions::DunderAllEntry::Name(_, n) if n == name))
+}
+
+/// Does `defs` (the candidate module) plausibly re-export `name` from `source`?
+fn reexports(defs: &Definitions, source: ModuleName, name: &Name) -> bool {
+    defs.import_sources.get(name) == Some(&source)
+        || defs.import_all.contains_key(&source)
+        || defs.dunder_all.iter().any(
+            |entry| matches!(entry, crate::export::definitions::DunderAllEntry::Module(_, m) if *m == source),
+        )
+}
+
+fn dotted_components(module: ModuleName) -> usize {
+    module.as_str().matches('.').count()
+}
+
+#[cfg(test)]
+mod tests {
+    use ruff_python_ast::Identifier;
+
+    use super::*;
+    use crate::ast::Ast;
+    use crate::config::Config;
+    use crate::module::module_info::ModuleInfo;
+
+    fn definitions(contents: &str, module: ModuleName) -> Definitions {
+        let ast = Ast::parse(contents).0;
+        Definitions::new(&ast.body, module, false, &Config::default())
+    }
+
+    fn qname(module: ModuleName, name: &str) -> QName {
+        QName::new(
+            Identifier::new(Name::new(name), TextRange::default()),
+            ModuleInfo::new(module),
+        )
+    }
+
+    #[test]
+    fn test_find_path_prefers_reexport() {
+        let mut modules = SmallMap::new();
+        let impl_module = ModuleName::from_str("pkg._impl");
+        let pkg = ModuleName::from_str("pkg");
+        modules.insert(
+            impl_module,
+            definitions("class Thing: ...", impl_module),
+        );
+        modules.insert(
+            pkg,
+            definitions("from pkg._impl import Thing as Thing", pkg),
+        );
+
+        let defining = qname(impl_module, "Thing");
+        let importer = ModuleName::from_str("caller");
+        let (found, _) = find_path(&modules, &defining, importer).unwrap();
+        assert_eq!(found.as_str(), "pkg");
+    }
+
+    #[test]
+    fn test_find_path_no_reexport() {
+        let mut modules = SmallMap::new();
+        let only = ModuleName::from_str("only");
+        modules.insert(only, definitions("class Thing: ...", only));
+
+        let defining = qname(only, "Thing");
+        let importer = ModuleName::from_str("caller");
+        let (found, _) = find_path(&modules, &defining, importer).unwrap();
+        assert_eq!(found.as_str(), "only");
+    }
+}
@@ -0,0 +1,134 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is licensed under the MIT license found in the
+ * LICENSE file in the root directory of this source tree.
+ */
+
+//! Expand `from x import *` into the concrete names it contributes, and
+//! flag names contributed by more than one star import with no explicit
+//! binding to shadow them.
+
+use ruff_python_ast::name::Name;
+use ruff_text_size::TextRange;
+use starlark_map::small_map::SmallMap;
+
+use crate::export::definitions::Definitions;
+use crate::module::module_name::ModuleName;
+
+/// A name contributed by two or more star imports, none of which is
+/// shadowed by an explicit local/import binding.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StarImportAmbiguity {
+    pub name: Name,
+    pub sources: Vec<(ModuleName, TextRange)>,
+}
+
+/// The result of expanding every `from x import *` in `definitions`.
+#[derive(Debug, Clone, Default)]
+pub struct ResolvedStarImports {
+    /// Every unshadowed name introduced into scope by a star import, and
+    /// where it came from. A name listed in `ambiguous` is bound here to the
+    /// first star-imported module that contributes it.
+    pub bindings: SmallMap<Name, (ModuleName, TextRange)>,
+    /// Names contributed by two or more star imports with nothing to
+    /// shadow them.
+    pub ambiguous: Vec<StarImportAmbiguity>,
+}
+
+/// Expand `definitions.import_all` using each target module's already-computed
+/// `Definitions` (found via `modules`). Following rustc's name-resolution
+/// rules: an explicit binding in `definitions.definitions` (any non-glob
+/// `DefinitionStyle`) shadows all glob contributions for that name with no
+/// error, but when two or more *different* star-imported modules contribute
+/// the same unshadowed name, that's an ambiguity.
+pub fn resolve_star_imports(
+    definitions: &Definitions,
+    modules: &SmallMap<ModuleName, Definitions>,
+) -> ResolvedStarImports {
+    let mut contributions: SmallMap<Name, Vec<(ModuleName, TextRange)>> = SmallMap::new();
+    for module in definitions.import_all.keys() {
+        let Some(defs) = modules.get(module) else {
+            continue;
+        };
+        for (name, range, _) in defs.public_names(*module, modules) {
+            contributions.entry(name).or_default().push((*module, range));
+        }
+    }
+
+    let mut result = ResolvedStarImports::default();
+    for (name, sources) in contributions {
+        if definitions.definitions.contains_key(&name) {
+            // Shadowed by an explicit binding in this module; no error.
+            continue;
+        }
+        if let [only] = sources.as_slice() {
+            result.bindings.insert(name, *only);
+        } else {
+            result.bindings.insert(name.clone(), sources[0]);
+            result
+                .ambiguous
+                .push(StarImportAmbiguity { name, sources });
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::Ast;
+    use crate::config::Config;
+
+    fn definitions(contents: &str, module: ModuleName) -> Definitions {
+        let ast = Ast::parse(contents).0;
+        Definitions::new(&ast.body, module, false, &Config::default())
+    }
+
+    #[test]
+    fn test_unambiguous_star_import() {
+        let foo = ModuleName::from_str("foo");
+        let mut modules = SmallMap::new();
+        modules.insert(foo, definitions("def thing(): ...", foo));
+
+        let main = definitions("from foo import *", ModuleName::from_str("main"));
+        let resolved = resolve_star_imports(&main, &modules);
+        assert!(resolved.ambiguous.is_empty());
+        assert_eq!(
+            resolved.bindings.get(&Name::new("thing")).map(|(m, _)| *m),
+            Some(foo),
+        );
+    }
+
+    #[test]
+    fn test_ambiguous_star_import() {
+        let foo = ModuleName::from_str("foo");
+        let bar = ModuleName::from_str("bar");
+        let mut modules = SmallMap::new();
+        modules.insert(foo, definitions("def thing(): ...", foo));
+        modules.insert(bar, definitions("def thing(): ...", bar));
+
+        let main = definitions(
+            "from foo import *\nfrom bar import *",
+            ModuleName::from_str("main"),
+        );
+        let resolved = resolve_star_imports(&main, &modules);
+        assert_eq!(resolved.ambiguous.len(), 1);
+        assert_eq!(resolved.ambiguous[0].name, Name::new("thing"));
+    }
+
+    #[test]
+    fn test_local_binding_shadows_star_import() {
+        let foo = ModuleName::from_str("foo");
+        let mut modules = SmallMap::new();
+        modules.insert(foo, definitions("def thing(): ...", foo));
+
+        let main = definitions(
+            "from foo import *\ndef thing(): ...",
+            ModuleName::from_str("main"),
+        );
+        let resolved = resolve_star_imports(&main, &modules);
+        assert!(resolved.ambiguous.is_empty());
+        assert!(resolved.bindings.get(&Name::new("thing")).is_none());
+    }
+}